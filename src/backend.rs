@@ -0,0 +1,295 @@
+use crate::Transition;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher as NotifyWatcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+
+/// Selects how a [`Watcher`](crate::Watcher) detects filesystem changes.
+#[derive(Debug, Default)]
+pub enum Backend {
+    /// Poll each target's metadata on every `watch()` call.
+    ///
+    /// This is the default: it has no setup cost and works everywhere, at
+    /// the expense of a `std::fs::metadata` call per target per `watch()`.
+    #[default]
+    Poll,
+    /// Subscribe to native OS filesystem notifications (inotify, FSEvents,
+    /// `ReadDirectoryChangesW`, ...) via the `notify` crate.
+    Native(NativeBackend),
+}
+
+/// The native-event half of [`Backend`].
+///
+/// Wraps a `notify::RecommendedWatcher` together with a map from watched
+/// path to target index, so incoming OS events can be translated back into
+/// the `(index, Transition)` pairs [`Watcher::watch`](crate::Watcher::watch)
+/// expects.
+pub struct NativeBackend {
+    watcher: RecommendedWatcher,
+    receiver: Receiver<notify::Result<Event>>,
+    paths: HashMap<PathBuf, usize>,
+    /// The directory watched on behalf of each tracked path (see `watch`).
+    dir_for: HashMap<PathBuf, PathBuf>,
+    /// Reference count of how many tracked paths rely on a given directory
+    /// being watched, so it's only unwatched once nothing under it needs it
+    /// any more.
+    watched_dirs: HashMap<PathBuf, usize>,
+}
+
+impl std::fmt::Debug for NativeBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NativeBackend")
+            .field("paths", &self.paths)
+            .finish()
+    }
+}
+
+impl NativeBackend {
+    /// Create a new native backend with nothing watched yet.
+    pub fn new() -> notify::Result<Self> {
+        let (sender, receiver) = channel();
+        let watcher = notify::recommended_watcher(sender)?;
+
+        Ok(Self {
+            watcher,
+            receiver,
+            paths: HashMap::new(),
+            dir_for: HashMap::new(),
+            watched_dirs: HashMap::new(),
+        })
+    }
+
+    /// Start watching `path`, associating it with `index` so later events
+    /// for that path are reported against the right target.
+    ///
+    /// We watch `path`'s parent directory rather than `path` itself. Besides
+    /// letting us watch a path that doesn't exist yet - a common case when
+    /// watching for a file to appear, which the OS APIs can't do directly -
+    /// it's the only way `notify` can pair a delete and a create into a
+    /// single rename event (see `drain`): that pairing is keyed off both
+    /// paths sharing the same watched directory, which a per-file watch on
+    /// `path` alone wouldn't give us.
+    pub fn watch(&mut self, path: &Path, index: usize) -> notify::Result<()> {
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        if !self.watched_dirs.contains_key(&dir) {
+            self.watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+        }
+        *self.watched_dirs.entry(dir.clone()).or_insert(0) += 1;
+        self.dir_for.insert(path.to_path_buf(), dir);
+
+        self.paths.insert(path.to_path_buf(), index);
+        Ok(())
+    }
+
+    /// Stop watching `path`.
+    pub fn unwatch(&mut self, path: &Path) {
+        self.paths.remove(path);
+
+        let Some(dir) = self.dir_for.remove(path) else {
+            return;
+        };
+
+        if let Some(refs) = self.watched_dirs.get_mut(&dir) {
+            *refs -= 1;
+            if *refs == 0 {
+                self.watched_dirs.remove(&dir);
+                let _ = self.watcher.unwatch(&dir);
+            }
+        }
+    }
+
+    /// Reindex every tracked path after the target at `removed_index` was
+    /// removed from the watcher's `targets`/`states` vectors, so the indices
+    /// recorded here stay in sync with the shifted-down vectors.
+    pub fn reindex_after_removal(&mut self, removed_index: usize) {
+        for index in self.paths.values_mut() {
+            if *index > removed_index {
+                *index -= 1;
+            }
+        }
+    }
+
+    /// Drain every pending OS event, translating it into `(target index,
+    /// Transition)` pairs. Events for paths we aren't tracking are ignored.
+    pub fn drain(&mut self) -> Vec<(usize, Transition)> {
+        let mut result = Vec::new();
+
+        while let Ok(event) = self.receiver.try_recv() {
+            let event = match event {
+                Ok(event) => event,
+                Err(_) => continue,
+            };
+
+            match event.kind {
+                EventKind::Create(_) => {
+                    self.push_for_paths(&event.paths, Transition::Created, &mut result);
+                }
+                EventKind::Remove(_) => {
+                    self.push_for_paths(&event.paths, Transition::Deleted, &mut result);
+                }
+                EventKind::Modify(ModifyKind::Metadata(_)) => {
+                    self.push_for_paths(&event.paths, Transition::MetadataChanged, &mut result);
+                }
+                // The OS paired a rename's "from" and "to" paths into a
+                // single event - report it as a `Renamed` against whichever
+                // side we're tracking.
+                EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                    if let [from, to] = event.paths.as_slice() {
+                        let index = self.paths.get(from).or_else(|| self.paths.get(to));
+                        if let Some(&index) = index {
+                            result.push((
+                                index,
+                                Transition::Renamed {
+                                    from: from.clone(),
+                                    to: to.clone(),
+                                },
+                            ));
+                        }
+                    }
+                }
+                // A lone rename-away or rename-into half isn't informative
+                // enough to report on its own - wait for the OS to deliver
+                // the paired `Both` event above.
+                EventKind::Modify(ModifyKind::Name(_)) => {}
+                EventKind::Modify(_) => {
+                    self.push_for_paths(&event.paths, Transition::Modified, &mut result);
+                }
+                _ => {}
+            }
+        }
+
+        result
+    }
+
+    /// Push `(target index, transition)` for every path in `paths` that
+    /// we're tracking.
+    fn push_for_paths(
+        &self,
+        paths: &[PathBuf],
+        transition: Transition,
+        result: &mut Vec<(usize, Transition)>,
+    ) {
+        for path in paths {
+            if let Some(&index) = self.paths.get(path) {
+                result.push((index, transition.clone()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::{Error, Write};
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    #[test]
+    /// Writing to a watched file should surface as a `Modified` transition
+    /// for the right target index.
+    fn drain_reports_modified_for_watched_index() -> Result<(), Error> {
+        let tmp = tempfile::NamedTempFile::new()?;
+        let mut native = NativeBackend::new().expect("native backend available");
+        native
+            .watch(tmp.path(), 0)
+            .expect("watching a temp file should succeed");
+
+        {
+            let mut handle = tmp.reopen()?;
+            write!(handle, "test")?;
+        }
+
+        // Give the OS a moment to deliver the event.
+        sleep(Duration::from_millis(200));
+
+        let events = native.drain();
+        assert!(events.contains(&(0, Transition::Modified)));
+
+        Ok(())
+    }
+
+    #[test]
+    /// Watching a path that doesn't exist yet should succeed, since it's the
+    /// parent directory that gets watched, so the file's later creation is
+    /// still reported.
+    fn watch_falls_back_to_parent_for_nonexistent_path() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("not_here_yet.txt");
+
+        let mut native = NativeBackend::new().expect("native backend available");
+        native
+            .watch(&file, 0)
+            .expect("watching a not-yet-existing path should fall back, not fail");
+
+        fs::write(&file, "test")?;
+
+        // Give the OS a moment to deliver the event.
+        sleep(Duration::from_millis(200));
+
+        let events = native.drain();
+        assert!(events.contains(&(0, Transition::Created)));
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    /// A permission change on a watched file should surface as
+    /// `MetadataChanged`, not the generic `Modified`.
+    fn drain_reports_metadata_changed_for_permission_change() -> Result<(), Error> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = tempfile::NamedTempFile::new()?;
+        let mut native = NativeBackend::new().expect("native backend available");
+        native
+            .watch(tmp.path(), 0)
+            .expect("watching a temp file should succeed");
+
+        let mut permissions = fs::metadata(tmp.path())?.permissions();
+        permissions.set_mode(permissions.mode() ^ 0o001);
+        fs::set_permissions(tmp.path(), permissions)?;
+
+        sleep(Duration::from_millis(200));
+
+        let events = native.drain();
+        assert!(events.contains(&(0, Transition::MetadataChanged)));
+
+        Ok(())
+    }
+
+    #[test]
+    /// Renaming a watched file should surface as a single `Renamed`
+    /// transition rather than a generic `Modified`.
+    fn drain_reports_renamed_for_watched_path() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let from = dir.path().join("original.txt");
+        let to = dir.path().join("renamed.txt");
+        fs::write(&from, "test")?;
+
+        let mut native = NativeBackend::new().expect("native backend available");
+        native
+            .watch(&from, 0)
+            .expect("watching an existing file should succeed");
+
+        fs::rename(&from, &to)?;
+
+        sleep(Duration::from_millis(200));
+
+        let events = native.drain();
+        assert!(events.contains(&(
+            0,
+            Transition::Renamed {
+                from: from.clone(),
+                to: to.clone(),
+            }
+        )));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use seahash::SeaHasher;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::Read;
+use std::path::Path;
+
+/// How much of the file we read into memory at a time while hashing.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Compute a cheap, non-cryptographic digest of `path`'s contents.
+///
+/// The file is read in fixed-size chunks so memory use stays bounded
+/// regardless of file size. Returns `None` if the file can't be read, for
+/// example if it was deleted between the existence check and this call.
+pub(crate) fn digest(path: &Path) -> Option<u64> {
+    let mut file = File::open(path).ok()?;
+    let mut hasher = SeaHasher::new();
+    let mut buffer = [0u8; CHUNK_SIZE];
+
+    loop {
+        let bytes_read = file.read(&mut buffer).ok()?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.write(&buffer[..bytes_read]);
+    }
+
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Error, Write};
+
+    #[test]
+    fn identical_contents_hash_the_same() -> Result<(), Error> {
+        let mut a = tempfile::NamedTempFile::new()?;
+        let mut b = tempfile::NamedTempFile::new()?;
+
+        write!(a, "the same content")?;
+        write!(b, "the same content")?;
+
+        assert_eq!(digest(a.path()), digest(b.path()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn different_contents_hash_differently() -> Result<(), Error> {
+        let mut a = tempfile::NamedTempFile::new()?;
+        let mut b = tempfile::NamedTempFile::new()?;
+
+        write!(a, "one")?;
+        write!(b, "two")?;
+
+        assert_ne!(digest(a.path()), digest(b.path()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_file_falls_back_to_none() {
+        assert_eq!(digest(Path::new("/does/not/exist")), None);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::Transition;
+
+/// Fold a freshly observed `incoming` transition into whatever is already
+/// `pending` for a target, per [`crate::Watcher::watch_debounced`]'s
+/// coalescing rules.
+///
+/// Returns `None` when the pending transition should be cleared outright
+/// (e.g. a file created and then deleted within the same debounce window
+/// nets out to nothing worth reporting).
+pub(crate) fn coalesce(pending: Option<Transition>, incoming: Transition) -> Option<Transition> {
+    match (pending, incoming) {
+        (None, transition) => Some(transition),
+        // A create immediately followed by a write is still just a create.
+        (Some(Transition::Created), Transition::Modified) => Some(Transition::Created),
+        // Created then deleted within the window cancels out entirely.
+        (Some(Transition::Created), Transition::Deleted) => None,
+        // Any other pending transition followed by a delete is superseded by
+        // the delete - there's no longer anything to report a modification of.
+        (Some(_), Transition::Deleted) => Some(Transition::Deleted),
+        // A real content modification or rename is more significant than a
+        // pending metadata-only change, so it supersedes it rather than
+        // being silently swallowed by the catch-all below.
+        (Some(Transition::MetadataChanged), incoming @ Transition::Modified)
+        | (Some(Transition::MetadataChanged), incoming @ Transition::Renamed { .. }) => {
+            Some(incoming)
+        }
+        // Repeated modifications (or anything else) collapse into whatever
+        // was already pending.
+        (Some(pending), _) => Some(pending),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn created_then_modified_stays_created() {
+        assert_eq!(
+            coalesce(Some(Transition::Created), Transition::Modified),
+            Some(Transition::Created)
+        );
+    }
+
+    #[test]
+    fn repeated_modifications_collapse() {
+        assert_eq!(
+            coalesce(Some(Transition::Modified), Transition::Modified),
+            Some(Transition::Modified)
+        );
+    }
+
+    #[test]
+    fn modified_then_deleted_cancels_the_modification() {
+        assert_eq!(
+            coalesce(Some(Transition::Modified), Transition::Deleted),
+            Some(Transition::Deleted)
+        );
+    }
+
+    #[test]
+    fn created_then_deleted_nets_to_nothing() {
+        assert_eq!(coalesce(Some(Transition::Created), Transition::Deleted), None);
+    }
+
+    #[test]
+    fn metadata_changed_then_modified_is_superseded_by_modified() {
+        assert_eq!(
+            coalesce(Some(Transition::MetadataChanged), Transition::Modified),
+            Some(Transition::Modified)
+        );
+    }
+
+    #[test]
+    fn metadata_changed_then_renamed_is_superseded_by_renamed() {
+        use std::path::PathBuf;
+
+        let from = PathBuf::from("a.txt");
+        let to = PathBuf::from("b.txt");
+
+        assert_eq!(
+            coalesce(
+                Some(Transition::MetadataChanged),
+                Transition::Renamed {
+                    from: from.clone(),
+                    to: to.clone()
+                }
+            ),
+            Some(Transition::Renamed { from, to })
+        );
+    }
+}
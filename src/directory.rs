@@ -0,0 +1,109 @@
+use crate::Watchable;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// A [`Watchable`] extension for types that watch more than a single path.
+///
+/// Where [`Watchable::path`] identifies the one path a target cares about,
+/// `RecursiveWatchable` additionally knows how to enumerate every path
+/// currently nested underneath it, so a [`Watcher`](crate::Watcher) can diff
+/// the set of descendants between calls.
+pub trait RecursiveWatchable: Watchable {
+    /// Walk the target and return every descendant path it currently covers.
+    fn scan(&self) -> Vec<PathBuf>;
+}
+
+/// Watches every file nested under a root directory.
+///
+/// Files are discovered by recursively walking `root`; a caller-supplied
+/// filter decides which paths are worth tracking at all, so things like
+/// `.git` directories, build output, or hidden files can be skipped.
+///
+/// The filter is called with the full path of each file found; directories
+/// themselves are always walked and never passed to it, so skipping a
+/// directory means rejecting the file paths underneath it.
+///
+/// # Examples
+///
+/// ```
+/// use fwatch::DirectoryTarget;
+///
+/// // Watch everything under "src", skipping files whose names start with a
+/// // dot. Files inside hidden directories are still tracked.
+/// let target = DirectoryTarget::new("src", |path| {
+///     !path
+///         .file_name()
+///         .and_then(|name| name.to_str())
+///         .map(|name| name.starts_with('.'))
+///         .unwrap_or(false)
+/// });
+/// ```
+pub struct DirectoryTarget {
+    /// The root of the directory tree we want to watch.
+    root: PathBuf,
+    /// Only paths for which this returns `true` are tracked.
+    filter: Box<dyn Fn(&Path) -> bool>,
+}
+
+impl DirectoryTarget {
+    /// Create a new directory target rooted at `root`, tracking only the
+    /// paths for which `filter` returns `true`.
+    pub fn new<T: Into<PathBuf>, F: Fn(&Path) -> bool + 'static>(root: T, filter: F) -> Self {
+        Self {
+            root: root.into(),
+            filter: Box::new(filter),
+        }
+    }
+}
+
+impl Watchable for DirectoryTarget {
+    fn path(&self) -> &PathBuf {
+        &self.root
+    }
+}
+
+impl RecursiveWatchable for DirectoryTarget {
+    fn scan(&self) -> Vec<PathBuf> {
+        WalkDir::new(&self.root)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| (self.filter)(path))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Error;
+
+    #[test]
+    /// Scanning a directory target should find nested files but skip anything
+    /// rejected by the filter.
+    fn scan_finds_nested_files_and_respects_filter() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let nested = dir.path().join("nested");
+        fs::create_dir(&nested)?;
+
+        fs::write(dir.path().join("keep.txt"), "a")?;
+        fs::write(nested.join("keep.txt"), "b")?;
+        fs::write(dir.path().join("skip.log"), "c")?;
+
+        let target = DirectoryTarget::new(dir.path(), |path| {
+            path.extension().and_then(|ext| ext.to_str()) == Some("txt")
+        });
+
+        let mut found = target.scan();
+        found.sort();
+
+        assert_eq!(
+            found,
+            vec![dir.path().join("keep.txt"), nested.join("keep.txt")]
+        );
+
+        Ok(())
+    }
+}
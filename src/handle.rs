@@ -0,0 +1,142 @@
+use crate::{Transition, Watchable, Watcher};
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Commands a [`WatchHandle`] can send to the background thread spawned by
+/// [`Watcher::spawn`].
+enum Command<W> {
+    AddTarget(W),
+    RemoveTarget(usize),
+    Shutdown,
+}
+
+/// A handle to a [`Watcher`] running on a background thread, returned by
+/// [`Watcher::spawn`].
+///
+/// Targets can still be added and removed while the watcher is running; the
+/// requests are forwarded to the background thread over an internal command
+/// channel and applied before its next poll.
+pub struct WatchHandle<W: Watchable + Send + 'static> {
+    commands: Sender<Command<W>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl<W: Watchable + Send + 'static> WatchHandle<W> {
+    /// Add a target to the watcher running on the background thread.
+    pub fn add_target(&self, target: W) {
+        let _ = self.commands.send(Command::AddTarget(target));
+    }
+
+    /// Remove the target at `index` from the watcher running on the
+    /// background thread.
+    pub fn remove_target(&self, index: usize) {
+        let _ = self.commands.send(Command::RemoveTarget(index));
+    }
+
+    /// Signal the background thread to stop and block until it has.
+    pub fn shutdown(mut self) {
+        let _ = self.commands.send(Command::Shutdown);
+
+        if let Some(join_handle) = self.join_handle.take() {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+impl<W: Watchable + Send + 'static> Watcher<W> {
+    /// Move this watcher onto a background thread that calls `watch()` every
+    /// `interval`, pushing `(target index, Transition)` pairs for every
+    /// non-[`Transition::None`] result down the returned channel.
+    ///
+    /// Returns a [`WatchHandle`] for adding/removing targets and for shutting
+    /// the thread down cleanly, alongside the `Receiver` half of the channel.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fwatch::{BasicTarget, Watcher};
+    /// use std::time::Duration;
+    ///
+    /// let mut watcher: Watcher<BasicTarget> = Watcher::new();
+    /// watcher.add_target(BasicTarget::new("foo.txt"));
+    ///
+    /// let (handle, transitions) = watcher.spawn(Duration::from_millis(200));
+    ///
+    /// for (index, transition) in transitions {
+    ///     // React to `transition` on target `index`.
+    ///     let _ = (index, transition);
+    /// }
+    ///
+    /// handle.shutdown();
+    /// ```
+    pub fn spawn(mut self, interval: Duration) -> (WatchHandle<W>, Receiver<(usize, Transition)>) {
+        let (command_tx, command_rx) = unbounded();
+        let (transition_tx, transition_rx) = unbounded();
+
+        let join_handle = thread::spawn(move || loop {
+            for command in command_rx.try_iter() {
+                match command {
+                    Command::AddTarget(target) => self.add_target(target),
+                    Command::RemoveTarget(index) => {
+                        self.remove_target(index);
+                    }
+                    Command::Shutdown => return,
+                }
+            }
+
+            for (index, transition) in self.watch().into_iter().enumerate() {
+                if transition != Transition::None
+                    && transition_tx.send((index, transition)).is_err()
+                {
+                    // The receiver was dropped; nothing left to do.
+                    return;
+                }
+            }
+
+            thread::sleep(interval);
+        });
+
+        (
+            WatchHandle {
+                commands: command_tx,
+                join_handle: Some(join_handle),
+            },
+            transition_rx,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BasicTarget, Transition, Watcher};
+    use std::fs;
+    use std::io::Error;
+    use std::time::Duration;
+
+    #[test]
+    /// A file created after the watcher is spawned should be reported on the
+    /// transition channel.
+    fn spawn_reports_transitions_on_channel() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("watched.txt");
+
+        let mut watcher: Watcher<BasicTarget> = Watcher::new();
+        watcher.add_target(BasicTarget::new(&file));
+
+        let (handle, transitions) = watcher.spawn(Duration::from_millis(20));
+
+        fs::write(&file, "test")?;
+
+        let (index, transition) = transitions
+            .recv_timeout(Duration::from_secs(2))
+            .expect("should observe a transition before timing out");
+
+        assert_eq!(index, 0);
+        assert_eq!(transition, Transition::Created);
+
+        handle.shutdown();
+
+        Ok(())
+    }
+}
@@ -1,5 +1,16 @@
+mod backend;
+mod content_hash;
+mod debounce;
+mod directory;
+mod handle;
+
+pub use backend::{Backend, NativeBackend};
+pub use directory::{DirectoryTarget, RecursiveWatchable};
+pub use handle::WatchHandle;
+
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 
 /// The base watchable trait.
 pub trait Watchable {
@@ -26,12 +37,23 @@ impl Watchable for BasicTarget {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 /// State transitions that a watchable may undergo.
 pub enum Transition {
     Created,
     Modified,
+    /// Only metadata changed - the modification time (and, with content
+    /// hashing, the content digest) is the same, but the file's
+    /// permission/mode bits or, exotically, its length differ.
+    MetadataChanged,
     Deleted,
+    /// A [`Transition::Deleted`] and [`Transition::Created`] observed in the
+    /// same [`Watcher::watch_recursive`] pass that share the same length
+    /// and, where available, inode/device identifiers - most likely a single
+    /// rename or move rather than two unrelated events. With the native
+    /// backend, `watch()` also reports a rename the OS delivers as a single
+    /// event this way.
+    Renamed { from: PathBuf, to: PathBuf },
     None,
 }
 
@@ -39,7 +61,22 @@ pub enum Transition {
 /// The current state of the watchable.
 pub enum WatchState {
     DoesNotExist,
-    Exists(Option<SystemTime>),
+    Exists {
+        /// The last modification time reported by the filesystem, if any.
+        modified: Option<SystemTime>,
+        /// A cheap content digest, present only when content hashing has
+        /// been enabled via [`Watcher::with_content_hash`].
+        digest: Option<u64>,
+        /// The file's length in bytes, if its metadata could be read.
+        len: Option<u64>,
+        /// The file's permission/mode bits, if its metadata could be read.
+        /// `None` on platforms without a meaningful mode (e.g. Windows).
+        mode: Option<u32>,
+        /// An (inode, device) pair identifying this file, used to pair a
+        /// delete with a create into a [`Transition::Renamed`]. `None` on
+        /// platforms without this concept.
+        identity: Option<(u64, u64)>,
+    },
 }
 
 #[derive(Debug, Default)]
@@ -49,33 +86,186 @@ pub enum WatchState {
 pub struct Watcher<W: Watchable> {
     targets: Vec<W>,
     states: Vec<WatchState>,
+    /// Per-target descendant state, used only by [`Watcher::watch_recursive`]
+    /// for targets that implement [`RecursiveWatchable`]. `None` until the
+    /// first `watch_recursive()` call establishes a baseline for that
+    /// target, so files already present when the target was added aren't
+    /// reported as [`Transition::Created`].
+    descendants: Vec<Option<HashMap<PathBuf, WatchState>>>,
+    /// How we detect changes: polling (the default) or native OS events.
+    backend: Backend,
+    /// The debounce quiet period, if [`Watcher::with_debounce`] has been
+    /// used. `None` means `watch_debounced` is unavailable.
+    debounce: Option<Duration>,
+    /// Per-target transition awaiting the quiet period to elapse, along with
+    /// the instant it was last updated. Only used when `debounce` is set.
+    pending: Vec<Option<(Transition, Instant)>>,
+    /// Whether to fall back to a content digest when mtime can't tell two
+    /// states apart. See [`Watcher::with_content_hash`].
+    hash_contents: bool,
+}
+
+/// Compute the current [`WatchState`] of `target`.
+///
+/// When `hash_contents` is set, the digest is always computed and stored,
+/// even on calls where the mtime comparison alone would already prove a
+/// change: this state may end up serving as the *baseline* for some future
+/// call whose mtime is ambiguous, and a missing digest there would silently
+/// break detection (`classify` only compares digests once both sides have
+/// one). `classify` already short-circuits on a differing mtime before ever
+/// looking at the digest, so storing it here costs a read but never costs
+/// correctness.
+fn compute_state<W: Watchable>(target: &W, hash_contents: bool) -> WatchState {
+    let path = target.path();
+
+    if !path.exists() {
+        return WatchState::DoesNotExist;
+    }
+
+    let metadata = std::fs::metadata(path).ok();
+
+    // Determine the last modification time of this file, if possible
+    let modified = metadata.as_ref().and_then(|metadata| metadata.modified().ok());
+    let len = metadata.as_ref().map(|metadata| metadata.len());
+    let mode = metadata.as_ref().and_then(file_mode);
+    let identity = metadata.as_ref().and_then(file_identity);
+
+    let digest = if hash_contents {
+        content_hash::digest(path)
+    } else {
+        None
+    };
+
+    WatchState::Exists {
+        modified,
+        digest,
+        len,
+        mode,
+        identity,
+    }
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode())
 }
 
-fn compute_state<W: Watchable>(target: &W) -> WatchState {
-    // Does the specified path exist
-    let file_exists = target.path().exists();
+#[cfg(not(unix))]
+fn file_mode(_metadata: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn file_identity(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.ino(), metadata.dev()))
+}
 
-    // Compute the last modification date of this file, if possible
-    let mut last_modified_date = None;
+#[cfg(not(unix))]
+fn file_identity(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
 
-    if file_exists {
-        // Determine the last modification time of this file
-        let metadata = std::fs::metadata(target.path());
+/// The outcome of comparing two [`WatchState::Exists`] states that aren't a
+/// creation or deletion.
+enum Classification {
+    None,
+    Modified,
+    MetadataChanged,
+}
 
-        if let Ok(metadata) = metadata {
-            if let Ok(modified) = metadata.modified() {
-                last_modified_date = Some(modified);
+/// Classify a transition between two "exists" states, per the rule: modified
+/// if mtime differs, or (mtime equal/absent and a digest is available on
+/// both sides) the digests differ; metadata-changed if only the length or
+/// mode bits differ; otherwise no change.
+fn classify(previous: &WatchState, current: &WatchState) -> Classification {
+    match (previous, current) {
+        (
+            WatchState::Exists {
+                modified: m1,
+                digest: d1,
+                len: l1,
+                mode: mo1,
+                ..
+            },
+            WatchState::Exists {
+                modified: m2,
+                digest: d2,
+                len: l2,
+                mode: mo2,
+                ..
+            },
+        ) => {
+            let mtime_differs = matches!((m1, m2), (Some(p), Some(c)) if p != c);
+            if mtime_differs {
+                return Classification::Modified;
+            }
+
+            let digest_differs = matches!((d1, d2), (Some(p), Some(c)) if p != c);
+            if digest_differs {
+                return Classification::Modified;
+            }
+
+            if l1 != l2 || mo1 != mo2 {
+                Classification::MetadataChanged
+            } else {
+                Classification::None
             }
         }
+        _ => Classification::None,
     }
+}
 
-    if file_exists {
-        WatchState::Exists(last_modified_date)
-    } else {
-        WatchState::DoesNotExist
+/// The (length, identity) signature used to pair a delete with a create into
+/// a [`Transition::Renamed`].
+type Signature = (Option<u64>, Option<(u64, u64)>);
+
+fn signature(state: &WatchState) -> Option<Signature> {
+    match state {
+        WatchState::Exists { len, identity, .. } => Some((*len, *identity)),
+        WatchState::DoesNotExist => None,
     }
 }
 
+/// Pair up `created`/`deleted` descendants that share a [`Signature`] into
+/// [`Transition::Renamed`] events, leaving the rest as plain
+/// `Created`/`Deleted`.
+fn pair_renames(
+    created: Vec<(PathBuf, Option<Signature>)>,
+    mut deleted: Vec<(PathBuf, Option<Signature>)>,
+) -> Vec<(PathBuf, Transition)> {
+    let mut result = Vec::new();
+
+    for (created_path, created_signature) in created {
+        let matching_delete = created_signature.and_then(|signature| {
+            deleted
+                .iter()
+                .position(|(_, deleted_signature)| *deleted_signature == Some(signature))
+        });
+
+        match matching_delete {
+            Some(position) => {
+                let (deleted_path, _) = deleted.remove(position);
+                result.push((
+                    created_path.clone(),
+                    Transition::Renamed {
+                        from: deleted_path,
+                        to: created_path,
+                    },
+                ));
+            }
+            None => result.push((created_path, Transition::Created)),
+        }
+    }
+
+    for (deleted_path, _) in deleted {
+        result.push((deleted_path, Transition::Deleted));
+    }
+
+    result
+}
+
 impl<W: Watchable> Watcher<W> {
     /// Create a new watcher instance.
     ///
@@ -92,9 +282,54 @@ impl<W: Watchable> Watcher<W> {
         Watcher {
             targets: Vec::new(),
             states: Vec::new(),
+            descendants: Vec::new(),
+            backend: Backend::Poll,
+            debounce: None,
+            pending: Vec::new(),
+            hash_contents: false,
         }
     }
 
+    /// Enable debounced transitions: subsequent calls to
+    /// [`Watcher::watch_debounced`] will coalesce bursts of raw transitions
+    /// into a single one per target, emitted once `quiet_period` has elapsed
+    /// with no further changes.
+    pub fn with_debounce(mut self, quiet_period: Duration) -> Self {
+        self.debounce = Some(quiet_period);
+        self
+    }
+
+    /// Fall back to a content digest when mtime alone can't tell two states
+    /// apart (for example on filesystems with coarse mtime granularity, or
+    /// after a restore that preserves the original mtime).
+    ///
+    /// This costs a read of the file's contents every time a target's state
+    /// is computed (the digest is stored even when mtime alone proves a
+    /// change, see `compute_state`), so it's opt-in; the default is the
+    /// lightweight mtime-only comparison.
+    pub fn with_content_hash(mut self) -> Self {
+        self.hash_contents = true;
+        self
+    }
+
+    /// Create a new watcher driven by native OS filesystem notifications
+    /// (inotify/FSEvents/`ReadDirectoryChangesW`) instead of polling.
+    ///
+    /// Targets added afterwards are registered with the OS watcher
+    /// automatically. On platforms or filesystems where native watching
+    /// isn't available, prefer [`Watcher::new`] and its poll-based `watch()`.
+    pub fn new_native() -> notify::Result<Self> {
+        Ok(Watcher {
+            targets: Vec::new(),
+            states: Vec::new(),
+            descendants: Vec::new(),
+            backend: Backend::Native(NativeBackend::new()?),
+            debounce: None,
+            pending: Vec::new(),
+            hash_contents: false,
+        })
+    }
+
     /// Adds a target to the watcher.
     ///
     /// # Examples
@@ -110,8 +345,14 @@ impl<W: Watchable> Watcher<W> {
     /// }
     /// ```
     pub fn add_target(&mut self, target: W) {
-        self.states.push(compute_state(&target));
+        if let Backend::Native(native) = &mut self.backend {
+            let _ = native.watch(target.path(), self.targets.len());
+        }
+
+        self.states.push(compute_state(&target, self.hash_contents));
         self.targets.push(target);
+        self.descendants.push(None);
+        self.pending.push(None);
     }
 
     /// Remove a target from the watcher.
@@ -137,8 +378,22 @@ impl<W: Watchable> Watcher<W> {
         if index > self.states.len() {
             false
         } else {
+            if let Backend::Native(native) = &mut self.backend {
+                if let Some(target) = self.targets.get(index) {
+                    native.unwatch(target.path());
+                }
+
+                // Every target after `index` is about to shift down by one
+                // in `targets`/`states`; keep the native backend's recorded
+                // indices in sync so it doesn't report events against the
+                // wrong target.
+                native.reindex_after_removal(index);
+            }
+
             self.states.remove(index);
             self.targets.remove(index);
+            self.descendants.remove(index);
+            self.pending.remove(index);
 
             true
         }
@@ -185,7 +440,7 @@ impl<W: Watchable> Watcher<W> {
     /// }
     /// ```
     pub fn get_path(&self, index: usize) -> Option<&PathBuf> {
-        self.targets.get(index).and_then(|v| Some(v.path()))
+        self.targets.get(index).map(|v| v.path())
     }
 
     /// Observe any state transitions in our targets.
@@ -213,33 +468,49 @@ impl<W: Watchable> Watcher<W> {
     ///         match transition {
     ///             Transition::Created => { /* The watched file has been created */ },
     ///             Transition::Modified => { /* The watched file has been modified */ },
+    ///             Transition::MetadataChanged => { /* Only metadata (mode/size) changed */ },
     ///             Transition::Deleted => { /* The watched file has been deleted */ },
+    ///             Transition::Renamed { .. } => { /* The watched file was renamed or moved */ },
     ///             Transition::None => { /* None of the above transitions were observed */ },
     ///         }
     ///     }
     /// }
     /// ```
     pub fn watch(&mut self) -> Vec<Transition> {
+        match &mut self.backend {
+            Backend::Poll => self.watch_poll(),
+            Backend::Native(_) => self.watch_native(),
+        }
+    }
+
+    /// The original poll-based implementation of `watch()`, used directly
+    /// when `backend` is [`Backend::Poll`].
+    fn watch_poll(&mut self) -> Vec<Transition> {
         let mut result = Vec::new();
+        let hash_contents = self.hash_contents;
 
         for (index, target) in self.targets.iter().enumerate() {
             let previous_state = self.states.get(index).unwrap();
-            let current_state = compute_state(target);
+            let current_state = compute_state(target, hash_contents);
             let mut transition = Transition::None;
 
             // Check for state transitions
             match (previous_state, &current_state) {
                 // The file was created
-                (WatchState::DoesNotExist, WatchState::Exists(_)) => {
+                (WatchState::DoesNotExist, WatchState::Exists { .. }) => {
                     transition = Transition::Created;
                 }
                 // The file was deleted
-                (WatchState::Exists(_), WatchState::DoesNotExist) => {
+                (WatchState::Exists { .. }, WatchState::DoesNotExist) => {
                     transition = Transition::Deleted;
                 }
-                // The file was modified
-                (WatchState::Exists(Some(t1)), WatchState::Exists(Some(t2))) if t1 != t2 => {
-                    transition = Transition::Modified;
+                // The file existed before and after - decide what changed, if anything
+                (WatchState::Exists { .. }, WatchState::Exists { .. }) => {
+                    transition = match classify(previous_state, &current_state) {
+                        Classification::Modified => Transition::Modified,
+                        Classification::MetadataChanged => Transition::MetadataChanged,
+                        Classification::None => Transition::None,
+                    };
                 }
                 _ => {}
             };
@@ -252,11 +523,185 @@ impl<W: Watchable> Watcher<W> {
 
         result
     }
+
+    /// Translate any OS events that have arrived since the last call into
+    /// `Transition`s, keyed by target index. Used when `backend` is
+    /// [`Backend::Native`].
+    fn watch_native(&mut self) -> Vec<Transition> {
+        let mut result = vec![Transition::None; self.targets.len()];
+
+        let events = match &mut self.backend {
+            Backend::Native(native) => native.drain(),
+            Backend::Poll => Vec::new(),
+        };
+
+        for (index, transition) in events {
+            if let Some(slot) = result.get_mut(index) {
+                *slot = transition;
+            }
+        }
+
+        // Keep our recorded state in sync for any target that changed, so
+        // `get_state` stays meaningful without polling every target.
+        let hash_contents = self.hash_contents;
+        for (index, transition) in result.iter().enumerate() {
+            if *transition != Transition::None {
+                if let Some(target) = self.targets.get(index) {
+                    let current_state = compute_state(target, hash_contents);
+                    *self.states.get_mut(index).unwrap() = current_state;
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Like [`Watcher::watch`], but coalesces bursts of raw transitions into
+    /// a single one per target, only reporting a target once `quiet_period`
+    /// (set via [`Watcher::with_debounce`]) has elapsed with no further
+    /// changes to it.
+    ///
+    /// Call this repeatedly, e.g. on the same cadence you'd call `watch()`;
+    /// a target with no pending transition, or one still inside its quiet
+    /// period, reports [`Transition::None`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Watcher::with_debounce`] hasn't been called.
+    pub fn watch_debounced(&mut self) -> Vec<Transition> {
+        let quiet_period = self
+            .debounce
+            .expect("watch_debounced requires with_debounce to be set first");
+
+        for (index, transition) in self.watch().into_iter().enumerate() {
+            if transition == Transition::None {
+                continue;
+            }
+
+            let slot = self.pending.get_mut(index).unwrap();
+            let previous = slot.take().map(|(transition, _)| transition);
+            *slot = debounce::coalesce(previous, transition)
+                .map(|coalesced| (coalesced, Instant::now()));
+        }
+
+        let mut result = vec![Transition::None; self.targets.len()];
+
+        for (index, slot) in self.pending.iter_mut().enumerate() {
+            let elapsed = match slot.as_ref() {
+                Some((_, last_updated)) => last_updated.elapsed() >= quiet_period,
+                None => false,
+            };
+
+            if elapsed {
+                if let Some((transition, _)) = slot.take() {
+                    result[index] = transition;
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl<W: RecursiveWatchable> Watcher<W> {
+    /// Observe state transitions for every descendant path of our recursive
+    /// targets, such as the files nested under a [`DirectoryTarget`].
+    ///
+    /// Returns, for each target (in target order), the list of `(path,
+    /// transition)` pairs observed for its descendants. A file that neither
+    /// appeared nor disappeared nor changed since the last call is simply
+    /// absent from that target's list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwatch::{DirectoryTarget, Watcher};
+    ///
+    /// fn main() {
+    ///     let mut watcher: Watcher<DirectoryTarget> = Watcher::new();
+    ///     watcher.add_target(DirectoryTarget::new("src", |_| true));
+    ///
+    ///     for (index, transitions) in watcher.watch_recursive().into_iter().enumerate() {
+    ///         for (path, transition) in transitions {
+    ///             // React to `path`'s `transition` under target `index`.
+    ///             let _ = (index, path, transition);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn watch_recursive(&mut self) -> Vec<Vec<(PathBuf, Transition)>> {
+        let mut result = Vec::new();
+        let hash_contents = self.hash_contents;
+
+        for (index, target) in self.targets.iter().enumerate() {
+            // `None` means this target has never been scanned before; seed
+            // its baseline silently instead of reporting every pre-existing
+            // descendant as a `Created`, mirroring how `add_target` captures
+            // a baseline state for non-recursive targets.
+            let Some(mut previous) = self.descendants[index].take() else {
+                let baseline = target
+                    .scan()
+                    .into_iter()
+                    .map(|path| {
+                        let state = compute_state(&BasicTarget::new(path.clone()), hash_contents);
+                        (path, state)
+                    })
+                    .collect();
+
+                self.descendants[index] = Some(baseline);
+                result.push(Vec::new());
+                continue;
+            };
+
+            let mut current = HashMap::new();
+            let mut transitions = Vec::new();
+            let mut created = Vec::new();
+
+            for path in target.scan() {
+                let previous_state = previous.remove(&path);
+                let current_state =
+                    compute_state(&BasicTarget::new(path.clone()), hash_contents);
+
+                match &previous_state {
+                    None => created.push((path.clone(), signature(&current_state))),
+                    Some(previous_state) => match classify(previous_state, &current_state) {
+                        Classification::Modified => {
+                            transitions.push((path.clone(), Transition::Modified));
+                        }
+                        Classification::MetadataChanged => {
+                            transitions.push((path.clone(), Transition::MetadataChanged));
+                        }
+                        Classification::None => {}
+                    },
+                }
+
+                current.insert(path, current_state);
+            }
+
+            // Anything left in `previous` vanished between the last scan and
+            // this one.
+            let deleted = previous
+                .into_iter()
+                .map(|(path, state)| (path, signature(&state)))
+                .collect();
+
+            // A delete paired with a create sharing the same length and
+            // identity is most likely a single rename, not two events.
+            transitions.extend(pair_renames(created, deleted));
+
+            self.descendants[index] = Some(current);
+            result.push(transitions);
+        }
+
+        result
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{BasicTarget, Transition, Watcher};
+    use crate::{BasicTarget, DirectoryTarget, Transition, Watcher};
+    use filetime::{set_file_mtime, FileTime};
+    use std::fs;
     use std::io::{Error, Write};
     use std::thread::sleep;
     use std::time::Duration;
@@ -291,4 +736,254 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    /// A file that already exists when a `DirectoryTarget` is added should
+    /// be part of the baseline, not reported as `Created` on the first poll.
+    fn recursive_pre_existing_file_is_not_reported_as_created() -> Result<(), Error> {
+        let mut watcher: Watcher<DirectoryTarget> = Watcher::new();
+
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("already_here.txt"), "test")?;
+
+        watcher.add_target(DirectoryTarget::new(dir.path(), |_| true));
+
+        assert_eq!(watcher.watch_recursive(), vec![Vec::new()]);
+
+        Ok(())
+    }
+
+    #[test]
+    /// Creates a file in a fresh subdirectory and tests that the recursive
+    /// watcher notices the addition.
+    fn recursive_transitions() -> Result<(), Error> {
+        let mut watcher: Watcher<DirectoryTarget> = Watcher::new();
+
+        let dir = tempfile::tempdir()?;
+        watcher.add_target(DirectoryTarget::new(dir.path(), |_| true));
+
+        // No descendants yet
+        assert_eq!(watcher.watch_recursive(), vec![Vec::new()]);
+
+        // Create a file in a brand new subdirectory
+        let nested = dir.path().join("nested");
+        fs::create_dir(&nested)?;
+        let file = nested.join("new.txt");
+        fs::write(&file, "test")?;
+
+        assert_eq!(
+            watcher.watch_recursive(),
+            vec![vec![(file.clone(), Transition::Created)]]
+        );
+
+        // Removing the file should be reported as a deletion
+        fs::remove_file(&file)?;
+
+        assert_eq!(
+            watcher.watch_recursive(),
+            vec![vec![(file, Transition::Deleted)]]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    /// Renaming a file within a watched directory should be reported as a
+    /// single `Renamed` transition rather than a delete paired with a
+    /// create.
+    fn rename_detection_pairs_delete_and_create() -> Result<(), Error> {
+        let mut watcher: Watcher<DirectoryTarget> = Watcher::new();
+
+        let dir = tempfile::tempdir()?;
+        watcher.add_target(DirectoryTarget::new(dir.path(), |_| true));
+
+        let original = dir.path().join("original.txt");
+        fs::write(&original, "same content, same inode")?;
+        watcher.watch_recursive();
+
+        let renamed = dir.path().join("renamed.txt");
+        fs::rename(&original, &renamed)?;
+
+        assert_eq!(
+            watcher.watch_recursive(),
+            vec![vec![(
+                renamed.clone(),
+                Transition::Renamed {
+                    from: original,
+                    to: renamed,
+                }
+            )]]
+        );
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    /// A permission change with no content or mtime change should be
+    /// reported as `MetadataChanged`, not `Modified`.
+    fn permission_only_change_is_metadata_changed() -> Result<(), Error> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut watcher: Watcher<BasicTarget> = Watcher::new();
+
+        let tmp = NamedTempFile::new()?;
+        watcher.add_target(BasicTarget::new(tmp.path()));
+
+        let mut permissions = fs::metadata(tmp.path())?.permissions();
+        permissions.set_mode(permissions.mode() ^ 0o001);
+        fs::set_permissions(tmp.path(), permissions)?;
+
+        assert_eq!(watcher.watch(), vec![Transition::MetadataChanged]);
+
+        Ok(())
+    }
+
+    #[test]
+    /// A transition observed inside the quiet period isn't reported until
+    /// the quiet period has elapsed with no further changes.
+    fn debounced_transitions_wait_for_quiet_period() -> Result<(), Error> {
+        let mut watcher: Watcher<BasicTarget> =
+            Watcher::new().with_debounce(Duration::from_millis(100));
+
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("debounced.txt");
+        watcher.add_target(BasicTarget::new(&file));
+
+        fs::write(&file, "one")?;
+
+        // Immediately after the write we're still inside the quiet period.
+        assert_eq!(watcher.watch_debounced(), vec![Transition::None]);
+
+        sleep(Duration::from_millis(150));
+
+        // Once the quiet period has elapsed with no further changes, the
+        // coalesced transition is finally reported.
+        assert_eq!(watcher.watch_debounced(), vec![Transition::Created]);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    /// A real content modification observed within the quiet period should
+    /// supersede a pending metadata-only change, not be swallowed by it.
+    fn debounced_content_modification_supersedes_pending_metadata_change() -> Result<(), Error> {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Content hashing makes the modification detectable even if the
+        // permission change and the write below land in the same mtime tick.
+        let mut watcher: Watcher<BasicTarget> = Watcher::new()
+            .with_debounce(Duration::from_millis(100))
+            .with_content_hash();
+
+        let tmp = NamedTempFile::new()?;
+        watcher.add_target(BasicTarget::new(tmp.path()));
+
+        let mut permissions = fs::metadata(tmp.path())?.permissions();
+        permissions.set_mode(permissions.mode() ^ 0o001);
+        fs::set_permissions(tmp.path(), permissions)?;
+
+        // Still inside the quiet period - a pending `MetadataChanged`.
+        assert_eq!(watcher.watch_debounced(), vec![Transition::None]);
+
+        {
+            let mut handle = tmp.reopen()?;
+            write!(handle, "test")?;
+        }
+
+        // The content change arrives within the same quiet period as the
+        // pending metadata change, so it should supersede it rather than be
+        // coalesced away.
+        assert_eq!(watcher.watch_debounced(), vec![Transition::None]);
+
+        sleep(Duration::from_millis(150));
+
+        // Once the quiet period elapses, the real content modification is
+        // reported - not the metadata-only change that preceded it.
+        assert_eq!(watcher.watch_debounced(), vec![Transition::Modified]);
+
+        Ok(())
+    }
+
+    #[test]
+    /// With content hashing enabled, a change should still be detected even
+    /// when the mtime is forced back to its original value.
+    fn content_hash_detects_modification_when_mtime_is_unchanged() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("stable_mtime.txt");
+        fs::write(&file, "one")?;
+        let original_mtime = FileTime::from_last_modification_time(&fs::metadata(&file)?);
+
+        let mut watcher: Watcher<BasicTarget> = Watcher::new().with_content_hash();
+        watcher.add_target(BasicTarget::new(&file));
+
+        fs::write(&file, "two")?;
+        set_file_mtime(&file, original_mtime)?;
+
+        // The mtime is identical to what it was when we started watching,
+        // but the content digest should catch the change anyway.
+        assert_eq!(watcher.watch(), vec![Transition::Modified]);
+
+        Ok(())
+    }
+
+    #[test]
+    /// With content hashing enabled, the digest stored on a change that
+    /// mtime alone proves should still serve as the baseline for a later
+    /// change that only the digest can detect.
+    fn content_hash_stays_valid_as_a_baseline_across_an_mtime_proven_change() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("double_edit.txt");
+        fs::write(&file, "one")?;
+
+        let mut watcher: Watcher<BasicTarget> = Watcher::new().with_content_hash();
+        watcher.add_target(BasicTarget::new(&file));
+
+        // First edit: a real mtime bump, so `watch()` detects it via mtime
+        // alone - the digest must still be refreshed and stored here, since
+        // it may need to serve as the baseline for a later, mtime-ambiguous
+        // comparison.
+        sleep(Duration::from_millis(1500));
+        fs::write(&file, "two")?;
+        let first_edit_mtime = FileTime::from_last_modification_time(&fs::metadata(&file)?);
+        assert_eq!(watcher.watch(), vec![Transition::Modified]);
+
+        // Second edit: force the mtime back to the first edit's value, so
+        // this change is only detectable via the content digest.
+        fs::write(&file, "three")?;
+        set_file_mtime(&file, first_edit_mtime)?;
+
+        assert_eq!(watcher.watch(), vec![Transition::Modified]);
+
+        Ok(())
+    }
+
+    #[test]
+    /// Removing a target shouldn't desync the native backend's index for
+    /// the targets that shift down to take its place.
+    fn remove_target_reindexes_native_backend() -> Result<(), Error> {
+        let first = NamedTempFile::new()?;
+        let second = NamedTempFile::new()?;
+
+        let mut watcher: Watcher<BasicTarget> =
+            Watcher::new_native().expect("native backend available");
+        watcher.add_target(BasicTarget::new(first.path()));
+        watcher.add_target(BasicTarget::new(second.path()));
+
+        assert!(watcher.remove_target(0));
+
+        {
+            let mut handle = second.reopen()?;
+            write!(handle, "test")?;
+        }
+
+        sleep(Duration::from_millis(200));
+
+        // `second` is now target 0; the transition must be reported against
+        // its new index, not the stale pre-removal one.
+        assert_eq!(watcher.watch(), vec![Transition::Modified]);
+
+        Ok(())
+    }
 }